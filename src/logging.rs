@@ -0,0 +1,114 @@
+use crate::{serial, terminal};
+use core::fmt::Arguments;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+/// Independently toggles whether a single sink receives log output.
+pub struct LoggerStatus(AtomicBool);
+
+impl LoggerStatus {
+    const fn new(enabled: bool) -> Self {
+        LoggerStatus(AtomicBool::new(enabled))
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, enabled: bool) {
+        self.0.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Dispatches formatted log output to the framebuffer and/or serial sinks, each of
+/// which can be toggled independently, gated by a shared [`LevelFilter`].
+pub struct Logger {
+    pub framebuffer: LoggerStatus,
+    pub serial: LoggerStatus,
+    level: AtomicUsize,
+}
+
+impl Logger {
+    const fn new() -> Self {
+        Logger {
+            framebuffer: LoggerStatus::new(true),
+            serial: LoggerStatus::new(true),
+            level: AtomicUsize::new(LevelFilter::Trace as usize),
+        }
+    }
+
+    /// The level filter currently gating log output.
+    pub fn level(&self) -> LevelFilter {
+        level_filter_from_usize(self.level.load(Ordering::Relaxed))
+    }
+
+    /// Updates the level filter used to gate log output.
+    pub fn set_level(&self, level: LevelFilter) {
+        self.level.store(level as usize, Ordering::Relaxed);
+        log::set_max_level(level);
+    }
+
+    /// Writes already-formatted output to every currently enabled sink.
+    pub fn dispatch(&self, args: Arguments) {
+        if self.framebuffer.enabled() {
+            if let Some(writer) = terminal::WRITER.get() {
+                let _ = writer.write_fmt(args);
+            }
+        }
+        if self.serial.enabled() {
+            if let Some(writer) = serial::SERIAL.get() {
+                let _ = writer.write_fmt(args);
+            }
+        }
+    }
+}
+
+fn level_filter_from_usize(value: usize) -> LevelFilter {
+    match value {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let prefix = match record.level() {
+            Level::Error => "[ERROR] ",
+            Level::Warn => "[WARN]  ",
+            Level::Info => "[INFO]  ",
+            Level::Debug => "[DEBUG] ",
+            Level::Trace => "[TRACE] ",
+        };
+        self.dispatch(format_args!("{}{}\n", prefix, record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+/// The global logging subsystem; fans out to whichever sinks are enabled.
+pub static LOGGER: Logger = Logger::new();
+
+/// Brings up the serial sink. Must be called once at boot, next to the framebuffer
+/// writer's own initialization.
+pub fn init() {
+    serial::SERIAL.get_or_init(|| unsafe { serial::LockedSerialWriter::new(serial::COM1_PORT) });
+}
+
+/// Registers [`LOGGER`] with the `log` crate so `info!`/`warn!`/`error!` work
+/// kernel-wide. Must be called once at boot, after [`init`].
+pub fn install() -> Result<(), SetLoggerError> {
+    log::set_logger(&LOGGER)?;
+    log::set_max_level(LOGGER.level());
+    Ok(())
+}
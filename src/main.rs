@@ -1,11 +1,18 @@
 #![no_std]
 #![no_main]
+#![feature(alloc_error_handler)]
+
+extern crate alloc;
 
 use bootloader::{entry_point, BootInfo};
-use core::arch::x86_64::_rdtsc;
 use core::panic::PanicInfo;
-use raw_cpuid::CpuId;
+use x86_64::VirtAddr;
+pub mod allocator;
+pub mod logging;
+pub mod memory;
+pub mod serial;
 pub mod terminal;
+pub mod time;
 
 entry_point!(kernel_main);
 
@@ -30,35 +37,78 @@ fn kernel_main(boot_info: &'static mut BootInfo) -> ! {
         terminal::WRITER
             .get_or_init(move || terminal::LockedWriter::new(framebuffer.buffer_mut(), info));
     }
-    let start_time = get_time();
-    printf!("Test\n");
-    let mut last_print = get_time();
+    logging::init();
+    logging::install().expect("logger already installed");
+
+    let phys_mem_offset = VirtAddr::new(
+        boot_info
+            .physical_memory_offset
+            .into_option()
+            .expect("bootloader did not provide a physical memory offset"),
+    );
+    let mut mapper = unsafe { memory::init(phys_mem_offset) };
+    let mut frame_allocator =
+        unsafe { memory::BootInfoFrameAllocator::init(&boot_info.memory_regions) };
+    allocator::init_heap(&mut mapper, &mut frame_allocator).expect("heap initialization failed");
+
+    {
+        let heap_value = alloc::boxed::Box::new(41);
+        let mut vec = alloc::vec::Vec::new();
+        for i in 0..10 {
+            vec.push(i);
+        }
+        assert_eq!(*heap_value, 41);
+        assert_eq!(vec.iter().sum::<i32>(), 45);
+        log::info!("heap allocator self-test passed");
+    }
+
+    time::init();
+    let start_time = time::now();
+    log::info!("Test");
+    let mut last_print = time::now();
     loop {
-        let time = get_time();
-        if time - last_print > 1000000000 {
-            printf!("Time: {}s\n", (time - start_time) / 1000000000);
-            last_print = time;
+        let now = time::now();
+        if now - last_print > 1_000_000_000 {
+            log::info!("Time: {}s", (now - start_time) / 1_000_000_000);
+            last_print = now;
         }
     }
 }
 
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    loop {}
-}
-
-fn get_time() -> u64 {
-    let mut time = 0;
-    // Check if the CPU supports `RDTSC`.
-    let cpu_id = CpuId::new();
-    if let Some(feature_info) = cpu_id.get_feature_info() {
-        if feature_info.has_tsc() {
-            let value = unsafe {
-                // SAFETY: We checked that the cpu supports `RDTSC` and we run in ring 0.
-                core::arch::x86_64::_rdtsc()
-            };
-            time = value;
+fn panic(info: &PanicInfo) -> ! {
+    // Panics are written directly to both sinks, bypassing `logging::LOGGER`'s
+    // enabled-flags: a panic must be visible even if routine logging has been
+    // toggled off on one of them.
+    //
+    // SAFETY: either sink's spinlock may be held by whatever was panicking, so both
+    // must be force-unlocked before we can write to them here.
+    if let Some(writer) = terminal::WRITER.get() {
+        unsafe { writer.force_unlock() };
+        writer.clear();
+        let _ = writer.write_fmt(format_args!("KERNEL PANIC: {}\n", info.message()));
+        if let Some(location) = info.location() {
+            let _ = writer.write_fmt(format_args!(
+                "  at {}:{}:{}\n",
+                location.file(),
+                location.line(),
+                location.column()
+            ));
+        }
+    }
+    if let Some(serial) = serial::SERIAL.get() {
+        unsafe { serial.force_unlock() };
+        let _ = serial.write_fmt(format_args!("KERNEL PANIC: {}\n", info.message()));
+        if let Some(location) = info.location() {
+            let _ = serial.write_fmt(format_args!(
+                "  at {}:{}:{}\n",
+                location.file(),
+                location.line(),
+                location.column()
+            ));
         }
     }
-    time
+    loop {
+        unsafe { core::arch::asm!("hlt") };
+    }
 }
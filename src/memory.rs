@@ -0,0 +1,76 @@
+//! Virtual memory management: page table access and physical frame allocation.
+
+use bootloader::boot_info::{MemoryRegionKind, MemoryRegions};
+use x86_64::{
+    structures::paging::{FrameAllocator, OffsetPageTable, PageTable, PhysFrame, Size4KiB},
+    PhysAddr, VirtAddr,
+};
+
+/// Returns a mutable reference to the active level 4 page table.
+///
+/// # Safety
+///
+/// The caller must guarantee that the complete physical memory is mapped to virtual
+/// memory at the passed `physical_memory_offset`. Also, this function must be only
+/// called once to avoid aliasing `&mut` references.
+unsafe fn active_level_4_table(physical_memory_offset: VirtAddr) -> &'static mut PageTable {
+    use x86_64::registers::control::Cr3;
+
+    let (level_4_table_frame, _) = Cr3::read();
+
+    let phys = level_4_table_frame.start_address();
+    let virt = physical_memory_offset + phys.as_u64();
+    let page_table_ptr: *mut PageTable = virt.as_mut_ptr();
+
+    unsafe { &mut *page_table_ptr }
+}
+
+/// Initializes a new `OffsetPageTable`.
+///
+/// # Safety
+///
+/// The caller must guarantee that the complete physical memory is mapped to virtual
+/// memory at the passed `physical_memory_offset`. Also, this function must be only
+/// called once to avoid aliasing `&mut` references.
+pub unsafe fn init(physical_memory_offset: VirtAddr) -> OffsetPageTable<'static> {
+    let level_4_table = unsafe { active_level_4_table(physical_memory_offset) };
+    unsafe { OffsetPageTable::new(level_4_table, physical_memory_offset) }
+}
+
+/// A `FrameAllocator` that returns usable frames from the bootloader's memory map.
+pub struct BootInfoFrameAllocator {
+    memory_regions: &'static MemoryRegions,
+    next: usize,
+}
+
+impl BootInfoFrameAllocator {
+    /// Creates a frame allocator from the bootloader's memory map.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that the passed memory map is valid. Specifically, all
+    /// frames marked `Usable` in it must actually be unused.
+    pub unsafe fn init(memory_regions: &'static MemoryRegions) -> Self {
+        BootInfoFrameAllocator {
+            memory_regions,
+            next: 0,
+        }
+    }
+
+    /// Returns an iterator over the usable frames specified in the memory map.
+    fn usable_frames(&self) -> impl Iterator<Item = PhysFrame> {
+        let regions = self.memory_regions.iter();
+        let usable_regions = regions.filter(|r| r.kind == MemoryRegionKind::Usable);
+        let addr_ranges = usable_regions.map(|r| r.start..r.end);
+        let frame_addresses = addr_ranges.flat_map(|r| r.step_by(4096));
+        frame_addresses.map(|addr| PhysFrame::containing_address(PhysAddr::new(addr)))
+    }
+}
+
+unsafe impl FrameAllocator<Size4KiB> for BootInfoFrameAllocator {
+    fn allocate_frame(&mut self) -> Option<PhysFrame> {
+        let frame = self.usable_frames().nth(self.next);
+        self.next += 1;
+        frame
+    }
+}
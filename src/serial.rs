@@ -0,0 +1,42 @@
+use conquer_once::spin::OnceCell;
+use core::fmt::{self, Arguments, Write};
+use spinning_top::Spinlock;
+use uart_16550::SerialPort;
+
+/// The global serial logger instance, used for debugging over COM1 when the
+/// framebuffer is unavailable or inconvenient (e.g. under QEMU).
+pub static SERIAL: OnceCell<LockedSerialWriter> = OnceCell::uninit();
+
+/// I/O port base of the first serial interface (COM1).
+pub const COM1_PORT: u16 = 0x3F8;
+
+/// A [`SerialPort`] instance protected by a spinlock.
+pub struct LockedSerialWriter(Spinlock<SerialPort>);
+
+impl LockedSerialWriter {
+    /// Creates a new instance that writes to the serial port at `port`, programming
+    /// its divisor, line control and FIFO registers before first use.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `port` is a valid, unused serial I/O port.
+    pub unsafe fn new(port: u16) -> Self {
+        let mut serial_port = unsafe { SerialPort::new(port) };
+        serial_port.init();
+        LockedSerialWriter(Spinlock::new(serial_port))
+    }
+
+    pub fn write_fmt(&self, args: Arguments) -> fmt::Result {
+        self.0.lock().write_fmt(args)
+    }
+
+    /// Force-unlocks the logger to prevent a deadlock.
+    ///
+    /// This method is not memory safe and should be only used when absolutely necessary.
+    pub unsafe fn force_unlock(&self) {
+        unsafe { self.0.force_unlock() };
+    }
+}
+
+unsafe impl Send for LockedSerialWriter {}
+unsafe impl Sync for LockedSerialWriter {}
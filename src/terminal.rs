@@ -4,7 +4,7 @@ use core::{
     fmt::{self, Write, Arguments},
     ptr,
 };
-use noto_sans_mono_bitmap::{get_bitmap, get_bitmap_width, BitmapChar, BitmapHeight, FontWeight};
+use noto_sans_mono_bitmap::{get_bitmap, BitmapChar, BitmapHeight, FontWeight};
 use spinning_top::Spinlock;
 
 /// The global logger instance used for the `log` crate.
@@ -17,6 +17,16 @@ pub struct LockedWriter(Spinlock<Writer>);
 const LINE_SPACING: usize = 0;
 /// Additional vertical space between separate log messages
 const LOG_SPACING: usize = 2;
+/// Height in pixels of a rendered line, matching the font's bitmap height.
+const CHAR_HEIGHT: usize = 14;
+/// Blank margin kept clear around the edges of the screen.
+const BORDER_PADDING: usize = 4;
+/// Additional horizontal space between characters.
+const LETTER_SPACING: usize = 0;
+/// Rendered in place of a character the font has no glyph for. Plain ASCII, since
+/// that's guaranteed to be covered regardless of which `noto-sans-mono-bitmap`
+/// unicode-block features happen to be enabled.
+const BACKUP_CHAR: char = '?';
 
 impl LockedWriter {
     /// Create a new instance that logs to the given framebuffer.
@@ -34,6 +44,11 @@ impl LockedWriter {
     pub unsafe fn force_unlock(&self) {
         unsafe { self.0.force_unlock() };
     }
+
+    /// Clears the screen, giving the writer a blank slate (used by the panic handler).
+    pub fn clear(&self) {
+        self.0.lock().clear();
+    }
 }
 
 /// Allows logging text to a pixel-based framebuffer.
@@ -50,15 +65,16 @@ impl Writer {
         let mut writer = Self {
             framebuffer,
             info,
-            x_pos: 0,
-            y_pos: 0,
+            x_pos: BORDER_PADDING,
+            y_pos: BORDER_PADDING,
         };
         writer.clear();
         writer
     }
 
     fn newline(&mut self) {
-        self.y_pos += 14 + LINE_SPACING;
+        self.y_pos += CHAR_HEIGHT + LINE_SPACING;
+        self.scroll_if_needed();
         self.carriage_return()
     }
 
@@ -67,16 +83,37 @@ impl Writer {
     }
 
     fn carriage_return(&mut self) {
-        self.x_pos = 0;
+        self.x_pos = BORDER_PADDING;
     }
 
     /// Erases all text on the screen.
     pub fn clear(&mut self) {
-        self.x_pos = 0;
-        self.y_pos = 0;
+        self.x_pos = BORDER_PADDING;
+        self.y_pos = BORDER_PADDING;
         self.framebuffer.fill(0);
     }
 
+    /// Shifts the framebuffer's interior contents (below the top border) up by one
+    /// line, blanking the freed bottom row, so older output scrolls off the top
+    /// instead of the whole screen being wiped. The top `BORDER_PADDING` rows are
+    /// left untouched rather than being shifted in along with everything else.
+    fn scroll(&mut self) {
+        let stride_bytes = self.info.stride * self.info.bytes_per_pixel;
+        let scroll_bytes = (CHAR_HEIGHT + LINE_SPACING) * stride_bytes;
+        let border_bytes = BORDER_PADDING * stride_bytes;
+        let total_bytes = self.framebuffer.len();
+        self.framebuffer
+            .copy_within(border_bytes + scroll_bytes..total_bytes, border_bytes);
+        self.framebuffer[total_bytes - scroll_bytes..].fill(0);
+        self.y_pos -= CHAR_HEIGHT + LINE_SPACING;
+    }
+
+    fn scroll_if_needed(&mut self) {
+        if self.y_pos + CHAR_HEIGHT + BORDER_PADDING > self.height() {
+            self.scroll();
+        }
+    }
+
     fn width(&self) -> usize {
         self.info.horizontal_resolution
     }
@@ -90,15 +127,13 @@ impl Writer {
             '\n' => self.newline(),
             '\r' => self.carriage_return(),
             c => {
-                if self.x_pos >= self.width() {
+                if self.x_pos + BORDER_PADDING >= self.width() {
                     self.newline();
                 }
-                const BITMAP_LETTER_WIDTH: usize =
-                    get_bitmap_width(FontWeight::Regular, BitmapHeight::Size14);
-                if self.y_pos >= (self.height() - BITMAP_LETTER_WIDTH) {
-                    self.clear();
-                }
-                let bitmap_char = get_bitmap(c, FontWeight::Regular, BitmapHeight::Size14).unwrap();
+                self.scroll_if_needed();
+                let bitmap_char = get_bitmap(c, FontWeight::Regular, BitmapHeight::Size14)
+                    .or_else(|| get_bitmap(BACKUP_CHAR, FontWeight::Regular, BitmapHeight::Size14))
+                    .expect("backup glyph must be renderable");
                 self.write_rendered_char(bitmap_char);
             }
         }
@@ -110,7 +145,7 @@ impl Writer {
                 self.write_pixel(self.x_pos + x, self.y_pos + y, *byte);
             }
         }
-        self.x_pos += rendered_char.width();
+        self.x_pos += rendered_char.width() + LETTER_SPACING;
     }
 
     fn write_pixel(&mut self, x: usize, y: usize, intensity: u8) {
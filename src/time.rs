@@ -0,0 +1,98 @@
+//! Monotonic timekeeping based on a calibrated TSC.
+//!
+//! `RDTSC` counts CPU cycles, not nanoseconds, and its frequency varies across
+//! machines, so raw cycle counts cannot be treated as a time unit directly. This
+//! module determines cycles-per-nanosecond once at boot (from CPUID leaf 0x15 where
+//! available, falling back to calibrating RDTSC against the PIT) and caches the
+//! result for all later conversions.
+
+use conquer_once::spin::OnceCell;
+use core::arch::x86_64::_rdtsc;
+use raw_cpuid::CpuId;
+use x86_64::instructions::port::Port;
+
+/// Cycles-per-nanosecond, computed once at boot.
+static CYCLES_PER_NS: OnceCell<f64> = OnceCell::uninit();
+
+/// Assumed frequency (GHz) used only if both CPUID and PIT calibration fail.
+const FALLBACK_GHZ: f64 = 1.0;
+
+/// Frequency of the legacy 8254 PIT, used to calibrate the TSC when CPUID leaf
+/// 0x15 doesn't report a crystal frequency (e.g. under QEMU's default `qemu64`/
+/// `kvm64` CPU models without `-cpu host`).
+const PIT_FREQUENCY_HZ: u64 = 1_193_182;
+/// Duration of the PIT calibration window.
+const CALIBRATION_MS: u64 = 10;
+
+/// Detects and caches the TSC frequency. Must be called once at boot before [`now`].
+pub fn init() {
+    CYCLES_PER_NS.get_or_init(detect_cycles_per_ns);
+}
+
+/// Returns the current time as nanoseconds since boot.
+///
+/// # Panics
+///
+/// Panics if [`init`] has not been called yet.
+pub fn now() -> u64 {
+    let cycles_per_ns = *CYCLES_PER_NS.get().expect("time::init was not called");
+    let cycles = unsafe {
+        // SAFETY: reaching here requires `init` to have cached a frequency, which
+        // only happens on CPUs that support `RDTSC`.
+        _rdtsc()
+    };
+    (cycles as f64 / cycles_per_ns) as u64
+}
+
+/// Reads the TSC/crystal-clock ratio from CPUID leaf 0x15, falling back to
+/// calibrating RDTSC against the PIT when that isn't available.
+fn detect_cycles_per_ns() -> f64 {
+    let cpu_id = CpuId::new();
+    if let Some(tsc_info) = cpu_id.get_tsc_info() {
+        if let Some(tsc_frequency_hz) = tsc_info.tsc_frequency() {
+            return tsc_frequency_hz as f64 / 1_000_000_000.0;
+        }
+    }
+    if let Some(cycles_per_ns) = calibrate_with_pit() {
+        return cycles_per_ns;
+    }
+    log::warn!(
+        "could not determine TSC frequency via CPUID or PIT calibration; assuming {}GHz",
+        FALLBACK_GHZ
+    );
+    FALLBACK_GHZ
+}
+
+/// Calibrates the TSC against PIT channel 2, gated through the keyboard
+/// controller's speaker port, over a fixed [`CALIBRATION_MS`] window.
+fn calibrate_with_pit() -> Option<f64> {
+    let reload = PIT_FREQUENCY_HZ * CALIBRATION_MS / 1000;
+    if reload == 0 || reload > u16::MAX as u64 {
+        return None;
+    }
+
+    unsafe {
+        let mut speaker: Port<u8> = Port::new(0x61);
+        let mut channel2: Port<u8> = Port::new(0x42);
+        let mut command: Port<u8> = Port::new(0x43);
+
+        // Channel 2, mode 0 (interrupt on terminal count), low+high byte, binary.
+        command.write(0b1011_0000u8);
+        channel2.write((reload & 0xFF) as u8);
+        channel2.write((reload >> 8) as u8);
+
+        // Disable the PC speaker output but enable the channel 2 gate so it starts
+        // counting down.
+        let speaker_state = speaker.read();
+        speaker.write((speaker_state & 0xFC) | 0x01);
+
+        let start_cycles = _rdtsc();
+        // Bit 5 of the speaker port reflects channel 2's output, which goes high
+        // once the countdown reaches zero.
+        while speaker.read() & 0x20 == 0 {}
+        let end_cycles = _rdtsc();
+
+        let elapsed_ns = CALIBRATION_MS * 1_000_000;
+        Some((end_cycles - start_cycles) as f64 / elapsed_ns as f64)
+    }
+}